@@ -9,7 +9,8 @@ fn main() -> Result<(), std::io::Error> {
         model_name: "gemma3".to_string(),
         temperature: Some(0.7), // Moderate creativity
         max_tokens: Some(100),  // Limit response length
-        api_url: String::new()
+        api_url: String::new(),
+        embedding_model_name: String::new()
     };
 
     // Send a simple message and get the response
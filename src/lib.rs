@@ -5,6 +5,7 @@ use indexmap::IndexMap;
 use atlas_http::{HttpClient, HttpBody, HttpRequest};
 use regex::Regex;
 use std::io;
+use std::fmt;
 
 // Represents a profile for interacting with an LLM provider's API
 #[derive(Default, Clone, Serialize, Deserialize)]
@@ -15,6 +16,7 @@ pub struct LlmProfile {
     pub temperature: Option<f32>, // Optional temperature setting for generation
     pub max_tokens: Option<usize>, // Optional maximum token limit
     pub api_url: String,          // Custom API URL (if empty, uses provider default)
+    pub embedding_model_name: String, // Model used by embed() (falls back to model_name if empty)
 }
 
 // Enum representing supported LLM providers
@@ -33,11 +35,142 @@ pub enum LlmProvider {
     other      // Custom or unspecified provider
 }
 
+// Typed, provider-aware error for every sermo operation, so callers can distinguish an auth
+// failure from a rate limit, a bad request, or a JSON-shape mismatch instead of matching on a
+// stringly-typed io::Error message
+#[derive(Debug)]
+pub enum SermoError {
+    Http { status: u16, body: String },        // Non-200 response that isn't one of the cases below
+    RateLimited { retry_after: Option<u64> },  // HTTP 429, with Retry-After in seconds if present
+    Auth,                                       // HTTP 401/403: invalid or missing credentials
+    Deserialize(String),                        // Response body didn't match the expected shape
+    EmptyResponse,                              // Provider returned no choices/content/candidates
+    Transport(String),                          // The underlying HTTP request itself failed
+    Unsupported(String),                        // This operation isn't implemented for the profile's provider
+}
+
+impl fmt::Display for SermoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SermoError::Http { status, body } => write!(f, "HTTP error {}: {}", status, body),
+            SermoError::RateLimited { retry_after: Some(secs) } => write!(f, "rate limited, retry after {}s", secs),
+            SermoError::RateLimited { retry_after: None } => write!(f, "rate limited"),
+            SermoError::Auth => write!(f, "authentication failed"),
+            SermoError::Deserialize(msg) => write!(f, "failed to parse response: {}", msg),
+            SermoError::EmptyResponse => write!(f, "provider returned an empty response"),
+            SermoError::Transport(msg) => write!(f, "transport error: {}", msg),
+            SermoError::Unsupported(msg) => write!(f, "unsupported: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for SermoError {}
+
+// Lets callers that already propagate io::Error (e.g. via `?` in a fn returning io::Result) keep
+// doing so without matching on SermoError explicitly
+impl From<SermoError> for io::Error {
+    fn from(e: SermoError) -> Self {
+        io::Error::new(io::ErrorKind::Other, e.to_string())
+    }
+}
+
+// OpenAI's JSON error envelope: `{"error": {"message": ..., "type": ...}}`
+#[derive(Deserialize)]
+struct OpenAiErrorEnvelope {
+    error: OpenAiErrorDetail,
+}
+
+#[derive(Deserialize)]
+struct OpenAiErrorDetail {
+    message: String,
+    #[serde(default)]
+    #[allow(dead_code)]
+    r#type: String,
+}
+
+// Ollama's JSON error envelope: `{"error": "..."}`
+#[derive(Deserialize)]
+struct OllamaErrorEnvelope {
+    error: String,
+}
+
+// Classifies a non-200 HTTP response into a SermoError, parsing the provider's JSON error
+// envelope (OpenAI's `error.message`, Ollama's `error`) into the message when present
+fn classify_http_error(status: u16, body: &str, retry_after: Option<u64>) -> SermoError {
+    if status == 429 {
+        return SermoError::RateLimited { retry_after };
+    }
+    if status == 401 || status == 403 {
+        return SermoError::Auth;
+    }
+    if let Ok(envelope) = serde_json::from_str::<OpenAiErrorEnvelope>(body) {
+        return SermoError::Http { status, body: envelope.error.message };
+    }
+    if let Ok(envelope) = serde_json::from_str::<OllamaErrorEnvelope>(body) {
+        return SermoError::Http { status, body: envelope.error };
+    }
+    SermoError::Http { status, body: body.to_string() }
+}
+
 // Represents a single message in a chat conversation
-#[derive(Serialize, Deserialize)]
-struct ChatMessage {
-    role: String,    // Role of the message sender (e.g., "user", "assistant")
-    content: String, // Message content
+#[derive(Default, Clone, Serialize, Deserialize)]
+pub struct ChatMessage {
+    pub role: String, // Role of the message sender (e.g., "user", "assistant", "system", "tool")
+    // Message content. Optional because OpenAI-compatible providers (OpenAI, Groq, xAI,
+    // Together, Mistral, Deepseek) send `"content": null` on assistant messages that carry a
+    // tool call instead of text.
+    #[serde(default)]
+    pub content: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>, // Set on role "tool" messages to identify which call this answers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_calls: Option<Vec<ToolCallWire>>, // Populated on assistant replies that invoke a tool
+}
+
+// A function the model may call, described to the provider as a JSON schema
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ToolDefinition {
+    pub name: String,               // Function name the model can invoke
+    pub description: String,        // What the function does, so the model knows when to call it
+    pub parameters: serde_json::Value, // JSON schema describing the function's arguments
+}
+
+// A tool invocation requested by the model, as handed back to the caller
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ToolCall {
+    pub id: String,                   // Identifier to echo back in the role "tool" reply
+    pub name: String,                 // Name of the invoked function
+    pub arguments: serde_json::Value, // Arguments the model supplied
+}
+
+// Outcome of a tool-enabled chat turn: either a plain text answer, or a function the model
+// wants invoked before it can continue
+#[derive(Clone)]
+pub enum ChatResult {
+    Text(String),
+    ToolCall(ToolCall),
+}
+
+// Wire shape of a tool call as returned by a provider's API
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ToolCallWire {
+    pub id: String,                    // Provider-assigned id for this call
+    pub function: ToolCallFunctionWire, // The function name and arguments being invoked
+}
+
+// Wire shape of the function portion of a tool call
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ToolCallFunctionWire {
+    pub name: String,               // Function name
+    pub arguments: serde_json::Value, // OpenAI encodes this as a JSON string; Ollama as a native object
+}
+
+// Owns an LlmProfile plus the running transcript of a multi-turn conversation, so callers can
+// carry history (and an optional system prompt) across calls instead of sending one-shot messages
+#[derive(Clone)]
+pub struct Conversation {
+    pub profile: LlmProfile,        // Profile used to send each turn
+    pub messages: Vec<ChatMessage>, // Full transcript, oldest first; index 0 is the system message if set
 }
 
 // Standard chat request structure for most providers
@@ -45,8 +178,13 @@ struct ChatMessage {
 struct ChatRequest {
     model: String,           // Model name to use
     messages: Vec<ChatMessage>, // List of messages in the conversation
+    stream: bool,            // Whether to stream the response (false for single response)
     temperature: Option<f32>,   // Temperature for generation
     max_tokens: Option<usize>,  // Maximum tokens to generate
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<serde_json::Value>>, // Functions the model may call
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<String>, // e.g. "auto", to let the model decide whether to call a tool
 }
 
 // Ollama-specific chat request structure
@@ -57,6 +195,33 @@ struct ChatRequest_Ollama {
     stream: bool,            // Whether to stream the response (false for single response)
     temperature: Option<f32>,   // Temperature for generation
     max_tokens: Option<usize>,  // Maximum tokens to generate
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<serde_json::Value>>, // Functions the model may call
+}
+
+// A single incremental delta from a standard (OpenAI-style) streamed response
+#[derive(Serialize, Deserialize)]
+struct StreamDelta {
+    content: Option<String>, // Text fragment for this delta, if any
+}
+
+// A single streamed choice wrapping a delta
+#[derive(Serialize, Deserialize)]
+struct StreamChoice {
+    delta: StreamDelta, // The incremental content for this choice
+}
+
+// One `data: {...}` line of an OpenAI-style SSE stream
+#[derive(Serialize, Deserialize)]
+struct StreamChunk {
+    choices: Vec<StreamChoice>, // Usually a single choice per chunk
+}
+
+// One newline-delimited JSON line of an Ollama streamed response
+#[derive(Serialize, Deserialize)]
+struct ChatChunk_Ollama {
+    message: ChatMessage, // Fragment of the assistant's reply
+    done: bool,           // True on the final line of the stream
 }
 
 // Represents a single response choice from the LLM
@@ -71,57 +236,548 @@ struct ChatResponse {
     choices: Vec<ChatChoice>, // List of response choices (usually one for single requests)
 }
 
+// Anthropic's `/v1/messages` response structure
+#[derive(Serialize, Deserialize)]
+struct AnthropicResponse {
+    content: Vec<AnthropicContentBlock>, // List of content blocks (usually one text block)
+}
+
+// A single content block within an Anthropic response
+#[derive(Serialize, Deserialize)]
+struct AnthropicContentBlock {
+    text: String, // The generated text
+}
+
+// Google Gemini's `generateContent` response structure
+#[derive(Serialize, Deserialize)]
+struct GeminiResponse {
+    candidates: Vec<GeminiCandidate>, // List of candidate replies (usually one)
+}
+
+// A single candidate reply within a Gemini response
+#[derive(Serialize, Deserialize)]
+struct GeminiCandidate {
+    content: GeminiContent, // The candidate's content
+}
+
+// The content of a single Gemini candidate
+#[derive(Serialize, Deserialize)]
+struct GeminiContent {
+    parts: Vec<GeminiPart>, // List of text parts (usually one)
+}
+
+// A single text part within Gemini content
+#[derive(Serialize, Deserialize)]
+struct GeminiPart {
+    text: String, // The generated text
+}
+
+// Request body for an embeddings call to OpenAI-style providers
+#[derive(Serialize, Deserialize)]
+struct EmbeddingRequest {
+    model: String,       // Model name to use
+    input: Vec<String>,  // Texts to embed
+}
+
+// OpenAI-style embeddings response
+#[derive(Serialize, Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingData>, // One entry per input text, in the same order
+}
+
+// A single embedding vector within an OpenAI-style embeddings response
+#[derive(Serialize, Deserialize)]
+struct EmbeddingData {
+    embedding: Vec<f32>, // The embedding vector
+}
+
+// Ollama's `/api/embeddings` request body (one prompt per call)
+#[derive(Serialize, Deserialize)]
+struct EmbeddingRequest_Ollama {
+    model: String,  // Model name to use
+    prompt: String, // Text to embed
+}
+
+// Ollama's `/api/embeddings` response body
+#[derive(Serialize, Deserialize)]
+struct EmbeddingResponse_Ollama {
+    embedding: Vec<f32>, // The embedding vector
+}
+
+// Response shape of Ollama's `GET /api/tags`
+#[derive(Serialize, Deserialize)]
+struct OllamaTagsResponse {
+    models: Vec<OllamaTagModel>, // Locally available models
+}
+
+// A single entry in Ollama's model list
+#[derive(Serialize, Deserialize)]
+struct OllamaTagModel {
+    name: String, // Model name (e.g. "gemma3:latest")
+}
+
+// Response shape of an OpenAI-compatible `GET /v1/models`
+#[derive(Serialize, Deserialize)]
+struct ModelsListResponse {
+    data: Vec<ModelListEntry>, // Models available to this API key
+}
+
+// A single entry in an OpenAI-compatible model list
+#[derive(Serialize, Deserialize)]
+struct ModelListEntry {
+    id: String, // Model id (e.g. "gpt-4o")
+}
+
+// Adapts the crate's provider-agnostic ChatRequest into a provider's own wire format and parses
+// that provider's raw response body back into plain reply text
+trait ProviderAdapter {
+    // Builds the provider-specific JSON request body
+    fn build_request(&self, request: &ChatRequest) -> serde_json::Value;
+
+    // Returns the HTTP headers required to authenticate with the provider
+    fn auth_headers(&self) -> Vec<String>;
+
+    // Parses the provider's raw JSON response body into the assistant's reply text
+    fn parse_response(&self, body: &str) -> Result<String, SermoError>;
+}
+
+// Adapter for OpenAI-compatible providers (OpenAI, xAI, Mistral, Deepseek, Groq, TogetherAI, and
+// any custom "other" endpoint that mirrors the OpenAI chat-completions shape)
+struct StandardAdapter {
+    api_key: String, // API key sent as a bearer token
+}
+
+impl ProviderAdapter for StandardAdapter {
+    fn build_request(&self, request: &ChatRequest) -> serde_json::Value {
+        serde_json::to_value(request).unwrap_or(serde_json::Value::Null)
+    }
+
+    fn auth_headers(&self) -> Vec<String> {
+        vec![format!("Authorization: Bearer {}", self.api_key)]
+    }
+
+    fn parse_response(&self, body: &str) -> Result<String, SermoError> {
+        let json_res: ChatResponse = serde_json::from_str(body)
+            .map_err(|e| SermoError::Deserialize(e.to_string()))?;
+        if json_res.choices.is_empty() {
+            return Err(SermoError::EmptyResponse);
+        }
+        Ok(json_res.choices[0].message.content.clone().unwrap_or_default())
+    }
+}
+
+// Adapter for Anthropic's `/v1/messages` API, which pulls the system prompt out into a
+// top-level `system` field, requires `max_tokens`, and authenticates via an `x-api-key` /
+// `anthropic-version` header pair instead of a bearer token
+struct AnthropicAdapter {
+    api_key: String, // API key sent via the x-api-key header
+}
+
+impl ProviderAdapter for AnthropicAdapter {
+    fn build_request(&self, request: &ChatRequest) -> serde_json::Value {
+        let system = request.messages.iter()
+            .find(|m| m.role == "system")
+            .and_then(|m| m.content.clone());
+        let messages: Vec<&ChatMessage> = request.messages.iter()
+            .filter(|m| m.role != "system")
+            .collect();
+
+        let mut value = serde_json::json!({
+            "model": request.model,
+            "messages": messages,
+            "max_tokens": request.max_tokens.unwrap_or(1024),
+        });
+        if let Some(system) = system {
+            value["system"] = serde_json::Value::String(system);
+        }
+        if let Some(temperature) = request.temperature {
+            value["temperature"] = serde_json::json!(temperature);
+        }
+        value
+    }
+
+    fn auth_headers(&self) -> Vec<String> {
+        vec![
+            format!("x-api-key: {}", self.api_key),
+            "anthropic-version: 2023-06-01".to_string(),
+        ]
+    }
+
+    fn parse_response(&self, body: &str) -> Result<String, SermoError> {
+        let json_res: AnthropicResponse = serde_json::from_str(body)
+            .map_err(|e| SermoError::Deserialize(e.to_string()))?;
+        json_res.content.into_iter().next()
+            .map(|block| block.text)
+            .ok_or(SermoError::EmptyResponse)
+    }
+}
+
+// Adapter for Google Gemini's `generateContent` API, which wraps messages as
+// `contents[].parts[].text` and reads the API key from the URL query string rather than a header
+struct GeminiAdapter;
+
+impl ProviderAdapter for GeminiAdapter {
+    fn build_request(&self, request: &ChatRequest) -> serde_json::Value {
+        let system = request.messages.iter()
+            .find(|m| m.role == "system")
+            .and_then(|m| m.content.clone());
+
+        // Gemini only recognizes "user" and "model" roles in `contents`; map our "assistant"
+        // onto "model", fold every other role (e.g. "tool") onto "user", and drop the system
+        // message, which is carried separately via `systemInstruction`.
+        let contents: Vec<serde_json::Value> = request.messages.iter()
+            .filter(|m| m.role != "system")
+            .map(|m| {
+                let role = if m.role == "assistant" { "model" } else { "user" };
+                serde_json::json!({
+                    "role": role,
+                    "parts": [{ "text": m.content.clone().unwrap_or_default() }],
+                })
+            })
+            .collect();
+
+        let mut value = serde_json::json!({ "contents": contents });
+        if let Some(system) = system {
+            value["systemInstruction"] = serde_json::json!({ "parts": [{ "text": system }] });
+        }
+        value
+    }
+
+    fn auth_headers(&self) -> Vec<String> {
+        Vec::new() // The API key is already embedded in the URL via ~api_key~
+    }
+
+    fn parse_response(&self, body: &str) -> Result<String, SermoError> {
+        let json_res: GeminiResponse = serde_json::from_str(body)
+            .map_err(|e| SermoError::Deserialize(e.to_string()))?;
+        json_res.candidates.into_iter().next()
+            .and_then(|c| c.content.parts.into_iter().next())
+            .map(|p| p.text)
+            .ok_or(SermoError::EmptyResponse)
+    }
+}
+
+impl Conversation {
+    // Creates a new conversation bound to the given profile with an empty transcript
+    pub fn new(profile: LlmProfile) -> Self {
+        Conversation { profile, messages: Vec::new() }
+    }
+
+    // Sets (or replaces) the system prompt, which is always kept as the first message
+    pub fn set_system(&mut self, content: &str) {
+        let message = ChatMessage { role: "system".to_string(), content: Some(content.to_string()), ..Default::default() };
+        if self.messages.first().map(|m| m.role.as_str()) == Some("system") {
+            self.messages[0] = message;
+        } else {
+            self.messages.insert(0, message);
+        }
+    }
+
+    // Appends a user message to the transcript
+    pub fn push_user(&mut self, content: &str) {
+        self.messages.push(ChatMessage { role: "user".to_string(), content: Some(content.to_string()), ..Default::default() });
+    }
+
+    // Sends the full transcript to the LLM and appends the assistant's reply to the history,
+    // so the next call includes this turn as prior context
+    pub fn send(&mut self) -> Result<String, SermoError> {
+        let reply = self.profile.send_messages(&self.messages)?;
+        self.messages.push(ChatMessage { role: "assistant".to_string(), content: Some(reply.clone()), ..Default::default() });
+        Ok(reply)
+    }
+}
+
 impl LlmProfile {
-    // Sends a single message to the LLM and returns the response
-    pub fn send_single(&self, message: &str) -> Result<String, io::Error> {
+    // Sends a full conversation transcript to the LLM and returns the assistant's reply
+    pub fn send_messages(&self, messages: &[ChatMessage]) -> Result<String, SermoError> {
         // Handle Ollama separately due to its unique API
         if self.provider == LlmProvider::ollama {
-            return self.send_ollama(message);
+            let request = ChatRequest_Ollama {
+                model: self.model_name.clone(),
+                stream: false,
+                messages: messages.to_vec(),
+                temperature: self.temperature,
+                max_tokens: self.max_tokens,
+                tools: None,
+            };
+            let json_str = serde_json::to_string(&request)
+                .map_err(|e| SermoError::Deserialize(e.to_string()))?;
+            return self.send(&json_str);
+        }
+
+        // Construct a provider-agnostic chat request and route it through the adapter for
+        // whichever provider this profile targets
+        let request = ChatRequest {
+            model: self.model_name.clone(),
+            messages: messages.to_vec(),
+            stream: false,
+            temperature: self.temperature,
+            max_tokens: self.max_tokens,
+            tools: None,
+            tool_choice: None,
+        };
+        self.send_via_adapter(&request)
+    }
+
+    // Sends a conversation transcript along with a set of callable tools. Returns either the
+    // model's text answer or a tool call it wants the caller to execute; feed the tool's result
+    // back as a new message with role "tool" (and `tool_call_id` set to the call's id) and send
+    // again to continue the conversation.
+    pub fn send_with_tools(&self, messages: &[ChatMessage], tools: &[ToolDefinition]) -> Result<ChatResult, SermoError> {
+        let tool_specs = Some(Self::build_tool_specs(tools));
+
+        if self.provider == LlmProvider::ollama {
+            let request = ChatRequest_Ollama {
+                model: self.model_name.clone(),
+                stream: false,
+                messages: messages.to_vec(),
+                temperature: self.temperature,
+                max_tokens: self.max_tokens,
+                tools: tool_specs,
+            };
+            let json_str = serde_json::to_string(&request)
+                .map_err(|e| SermoError::Deserialize(e.to_string()))?;
+            let auth_header = format!("Authorization: Bearer {}", self.api_key);
+            let body = self.post(&json_str, &[auth_header])?;
+            return Self::parse_tool_response(&body, true);
         }
 
-        // Construct a standard chat request
+        let request = ChatRequest {
+            model: self.model_name.clone(),
+            messages: messages.to_vec(),
+            stream: false,
+            temperature: self.temperature,
+            max_tokens: self.max_tokens,
+            tools: tool_specs,
+            tool_choice: Some("auto".to_string()),
+        };
+        let adapter = self.get_adapter();
+        let value = adapter.build_request(&request);
+        let json_str = serde_json::to_string(&value)
+            .map_err(|e| SermoError::Deserialize(e.to_string()))?;
+        let body = self.post(&json_str, &adapter.auth_headers())?;
+        Self::parse_tool_response(&body, false)
+    }
+
+    // Builds the `{type: "function", function: {...}}` wire shape tool calling providers expect
+    fn build_tool_specs(tools: &[ToolDefinition]) -> Vec<serde_json::Value> {
+        tools.iter().map(|t| serde_json::json!({
+            "type": "function",
+            "function": {
+                "name": t.name,
+                "description": t.description,
+                "parameters": t.parameters,
+            }
+        })).collect()
+    }
+
+    // Parses a tool-enabled response into either a text answer or the first requested tool call
+    fn parse_tool_response(body: &str, is_ollama: bool) -> Result<ChatResult, SermoError> {
+        let message = if is_ollama {
+            // Ollama's /api/chat returns a single ChatChoice directly, not wrapped in `choices`
+            let json_res: ChatChoice = serde_json::from_str(body)
+                .map_err(|e| SermoError::Deserialize(e.to_string()))?;
+            json_res.message
+        } else {
+            let json_res: ChatResponse = serde_json::from_str(body)
+                .map_err(|e| SermoError::Deserialize(e.to_string()))?;
+            if json_res.choices.is_empty() {
+                return Err(SermoError::EmptyResponse);
+            }
+            json_res.choices.into_iter().next().unwrap().message
+        };
+
+        if let Some(call) = message.tool_calls.and_then(|calls| calls.into_iter().next()) {
+            return Ok(ChatResult::ToolCall(ToolCall {
+                id: call.id,
+                name: call.function.name,
+                arguments: Self::normalize_tool_arguments(call.function.arguments),
+            }));
+        }
+        Ok(ChatResult::Text(message.content.unwrap_or_default()))
+    }
+
+    // OpenAI encodes tool call arguments as a JSON-in-a-string; Ollama already sends a native
+    // object. Normalize both into a parsed serde_json::Value.
+    fn normalize_tool_arguments(value: serde_json::Value) -> serde_json::Value {
+        match value {
+            serde_json::Value::String(s) => serde_json::from_str(&s).unwrap_or(serde_json::Value::String(s)),
+            other => other,
+        }
+    }
+
+    // Embeds a batch of texts into vectors, for semantic search / RAG use cases. Uses
+    // `embedding_model_name` if set, otherwise falls back to `model_name`.
+    //
+    // Only Ollama and OpenAI-compatible providers (OpenAI, xAI, Mistral, Deepseek, Groq,
+    // TogetherAI, "other") are supported: Anthropic has no embeddings endpoint, and Gemini's
+    // `:embedContent` uses a request/response shape this method doesn't implement.
+    pub fn embed(&self, input: &[&str]) -> Result<Vec<Vec<f32>>, SermoError> {
+        if matches!(self.provider, LlmProvider::anthropic | LlmProvider::google) {
+            return Err(SermoError::Unsupported(format!("{} does not support embed()", self.provider.to_string())));
+        }
+
+        let model = if self.embedding_model_name.is_empty() {
+            self.model_name.clone()
+        } else {
+            self.embedding_model_name.clone()
+        };
+
+        let mut url = self.provider.get_embedding_url();
+        url = url.replace("~model~", &model);
+        url = url.replace("~api_key~", &self.api_key);
+        let auth_header = format!("Authorization: Bearer {}", self.api_key);
+
+        // Ollama's /api/embeddings only accepts a single prompt per call, so embed one at a time
+        if self.provider == LlmProvider::ollama {
+            let mut vectors = Vec::with_capacity(input.len());
+            for text in input {
+                let request = EmbeddingRequest_Ollama {
+                    model: model.clone(),
+                    prompt: text.to_string(),
+                };
+                let json_str = serde_json::to_string(&request)
+                    .map_err(|e| SermoError::Deserialize(e.to_string()))?;
+                let body = self.post_to(&url, &json_str, &[auth_header.clone()])?;
+                let parsed: EmbeddingResponse_Ollama = serde_json::from_str(&body)
+                    .map_err(|e| SermoError::Deserialize(e.to_string()))?;
+                vectors.push(parsed.embedding);
+            }
+            return Ok(vectors);
+        }
+
+        let request = EmbeddingRequest {
+            model,
+            input: input.iter().map(|s| s.to_string()).collect(),
+        };
+        let json_str = serde_json::to_string(&request)
+            .map_err(|e| SermoError::Deserialize(e.to_string()))?;
+        let body = self.post_to(&url, &json_str, &[auth_header])?;
+        let parsed: EmbeddingResponse = serde_json::from_str(&body)
+            .map_err(|e| SermoError::Deserialize(e.to_string()))?;
+        Ok(parsed.data.into_iter().map(|d| d.embedding).collect())
+    }
+
+    // Returns the ProviderAdapter responsible for translating requests/responses for this
+    // profile's provider
+    fn get_adapter(&self) -> Box<dyn ProviderAdapter> {
+        match self.provider {
+            LlmProvider::anthropic => Box::new(AnthropicAdapter { api_key: self.api_key.clone() }),
+            LlmProvider::google => Box::new(GeminiAdapter),
+            _ => Box::new(StandardAdapter { api_key: self.api_key.clone() }),
+        }
+    }
+
+    // Builds the provider's request body via its adapter, sends it, and parses the reply
+    fn send_via_adapter(&self, request: &ChatRequest) -> Result<String, SermoError> {
+        let adapter = self.get_adapter();
+        let value = adapter.build_request(request);
+        let json_str = serde_json::to_string(&value)
+            .map_err(|e| SermoError::Deserialize(e.to_string()))?;
+        let body = self.post(&json_str, &adapter.auth_headers())?;
+        adapter.parse_response(&body)
+    }
+
+    // Sends a single message to the LLM and returns the response
+    pub fn send_single(&self, message: &str) -> Result<String, SermoError> {
+        let mut conversation = Conversation::new(self.clone());
+        conversation.push_user(message);
+        conversation.send()
+    }
+
+    // Requests the provider's streaming ("stream": true) completion shape and replays it to
+    // `on_chunk` one text fragment at a time, returning the fully concatenated reply.
+    //
+    // NOTE: `atlas_http`'s sync client (`build_sync`) has no incremental body read, so this
+    // waits for the full response before parsing it — `on_chunk` fires once per SSE/NDJSON
+    // line of the completed body that carries a text fragment, not as bytes arrive over the
+    // network. It exists to let
+    // callers process the reply line-by-line instead of waiting on a single combined string;
+    // it is not a low-latency, arrives-as-the-model-generates stream.
+    //
+    // Supports Ollama and the OpenAI-compatible providers only; Anthropic and Gemini stream in
+    // their own wire formats that the OpenAI-shaped SSE parser below doesn't understand, so
+    // those return `SermoError::Unsupported`.
+    pub fn send_single_stream<F: FnMut(&str)>(&self, message: &str, on_chunk: F) -> Result<String, SermoError> {
+        if self.provider == LlmProvider::ollama {
+            return self.send_ollama_stream(message, on_chunk);
+        }
+        if matches!(self.provider, LlmProvider::anthropic | LlmProvider::google) {
+            // `send_stream`/`parse_stream_line` only understand the OpenAI SSE shape; Anthropic
+            // and Gemini stream in their own incompatible formats, and routing through
+            // `get_adapter()` would still post an OpenAI-shaped chunked request. Rather than
+            // send it through anyway and fail at parse time, refuse up front.
+            return Err(SermoError::Unsupported(format!("{} does not support send_single_stream()", self.provider.to_string())));
+        }
+
+        // Construct a standard chat request with streaming enabled
         let request = ChatRequest {
             model: self.model_name.clone(),
             messages: vec![ChatMessage {
                 role: "user".to_string(),
-                content: message.to_string(),
+                content: Some(message.to_string()),
+                ..Default::default()
             }],
+            stream: true,
             temperature: self.temperature,
             max_tokens: self.max_tokens,
+            tools: None,
+            tool_choice: None,
         };
 
-        // Serialize the request to JSON
         let json_str = serde_json::to_string(&request)
-            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
-        self.send(&json_str)
+            .map_err(|e| SermoError::Deserialize(e.to_string()))?;
+        self.send_stream(&json_str, false, on_chunk)
     }
 
     // Sends a single message to Ollama's API and returns the response
-    pub fn send_ollama(&self, message: &str) -> Result<String, io::Error> {
-        // Construct an Ollama-specific chat request
+    pub fn send_ollama(&self, message: &str) -> Result<String, SermoError> {
+        self.send_messages(&[ChatMessage {
+            role: "user".to_string(),
+            content: Some(message.to_string()),
+            ..Default::default()
+        }])
+    }
+
+    // Sends a single message to Ollama's API and replays its NDJSON stream to `on_chunk` one
+    // line at a time. Same caveat as `send_single_stream`: the body is fetched in full first,
+    // so this is buffered replay, not delivery as Ollama generates tokens.
+    pub fn send_ollama_stream<F: FnMut(&str)>(&self, message: &str, on_chunk: F) -> Result<String, SermoError> {
+        // Construct an Ollama-specific chat request with streaming enabled
         let request = ChatRequest_Ollama {
             model: self.model_name.clone(),
-            stream: false, // Non-streaming response
+            stream: true,
             messages: vec![ChatMessage {
                 role: "user".to_string(),
-                content: message.to_string(),
+                content: Some(message.to_string()),
+                ..Default::default()
             }],
             temperature: self.temperature,
             max_tokens: self.max_tokens,
+            tools: None,
         };
 
-        // Serialize the request to JSON
         let json_str = serde_json::to_string(&request)
-            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
-        self.send(&json_str)
+            .map_err(|e| SermoError::Deserialize(e.to_string()))?;
+        self.send_stream(&json_str, true, on_chunk)
     }
 
-    // Internal method to send an HTTP request to the LLM provider's API
-    fn send(&self, json_str: &str) -> Result<String, io::Error> {
-        // Prepare the request body
-        let body = HttpBody::from_raw_str(json_str);
+    // Internal method to send an HTTP request to Ollama's API (the only provider not routed
+    // through a ProviderAdapter, since it has its own request/response shape already)
+    fn send(&self, json_str: &str) -> Result<String, SermoError> {
         let auth_header = format!("Authorization: Bearer {}", self.api_key);
-        
+        let body = self.post(json_str, &[auth_header])?;
+
+        // Ollama returns a single ChatChoice directly
+        let json_res: ChatChoice = serde_json::from_str(&body)
+            .map_err(|e| SermoError::Deserialize(e.to_string()))?;
+        Ok(json_res.message.content.clone().unwrap_or_default())
+    }
+
+    // Performs an HTTP POST to the provider's completion API (the custom `api_url` if one is set,
+    // else the provider default, with `~model~`/`~api_key~` substituted into either) and returns
+    // the raw response body
+    fn post(&self, json_str: &str, headers: &[String]) -> Result<String, SermoError> {
         // Determine the API URL (custom or provider default)
         let mut url = if self.api_url.is_empty() {
             self.provider.get_completion_url()
@@ -133,50 +789,99 @@ impl LlmProfile {
         url = url.replace("~model~", &self.model_name);
         url = url.replace("~api_key~", &self.api_key);
 
+        self.post_to(&url, json_str, headers)
+    }
+
+    // Performs the raw HTTP POST to an already-resolved URL and returns the response body
+    fn post_to(&self, url: &str, json_str: &str, headers: &[String]) -> Result<String, SermoError> {
+        // Prepare the request body
+        let body = HttpBody::from_raw_str(json_str);
+        let header_refs: Vec<&str> = headers.iter().map(|h| h.as_str()).collect();
+
         // Build and send the HTTP POST request
-        let req = HttpRequest::new("POST", &url, &vec![&auth_header.as_str()], &body);
+        let req = HttpRequest::new("POST", url, &header_refs, &body);
         let mut http = HttpClient::builder().browser().build_sync();
         let res = http.send(&req)
-            .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+            .map_err(|e| SermoError::Transport(e.to_string()))?;
 
-        // Check for HTTP success status
+        // Check for HTTP success status, surfacing Retry-After for 429s
         if res.status_code() != 200 {
-            return Err(io::Error::new(
-                io::ErrorKind::Other,
-                format!("HTTP error: {}", res.status_code())
-            ));
+            let retry_after = res.header("Retry-After").and_then(|v| v.parse::<u64>().ok());
+            return Err(classify_http_error(res.status_code(), &res.body(), retry_after));
         }
 
-        // Handle response based on provider
-        if self.provider == LlmProvider::ollama {
-            // Ollama returns a single ChatChoice directly
-            let json_res: ChatChoice = serde_json::from_str(&res.body())
-                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
-            Ok(json_res.message.content.clone())
-        } else {
-            // Other providers return a ChatResponse with choices
-            let json_res: ChatResponse = serde_json::from_str(&res.body())
-                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
-            if json_res.choices.is_empty() {
-                return Err(io::Error::new(
-                    io::ErrorKind::Other,
-                    "No choices in response"
-                ));
+        Ok(res.body())
+    }
+
+    // Requests the provider's "stream": true completion shape, then splits the (fully
+    // buffered) response body into its SSE/NDJSON lines and hands each one to the
+    // per-provider delta parser, invoking `on_chunk` per fragment. `atlas_http`'s sync client
+    // only exposes the complete response body (see `post_to`), so there is no network-level
+    // partial read to buffer here — the line splitting below is just body.split('\n'), kept
+    // as an explicit loop so it can later be swapped for a true incremental reader without
+    // changing `parse_stream_line` or the `on_chunk` contract.
+    fn send_stream<F: FnMut(&str)>(&self, json_str: &str, is_ollama: bool, mut on_chunk: F) -> Result<String, SermoError> {
+        let auth_header = format!("Authorization: Bearer {}", self.api_key);
+        let body = self.post(json_str, &[auth_header])?;
+        let mut full = String::new();
+        let mut buffer = String::new();
+        for c in body.chars() {
+            buffer.push(c);
+            if c == '\n' {
+                let line = buffer.clone();
+                buffer.clear();
+                if let Some(fragment) = Self::parse_stream_line(&line, is_ollama) {
+                    on_chunk(&fragment);
+                    full.push_str(&fragment);
+                }
+            }
+        }
+        // Handle a final line with no trailing newline
+        if !buffer.trim().is_empty() {
+            if let Some(fragment) = Self::parse_stream_line(&buffer, is_ollama) {
+                on_chunk(&fragment);
+                full.push_str(&fragment);
             }
-            Ok(json_res.choices[0].message.content.clone())
         }
+
+        Ok(full)
+    }
+
+    // Parses a single line of a streamed response into a text fragment, or None if the line
+    // carries no content (blank, a `[DONE]` sentinel, or a final Ollama `done: true` line)
+    fn parse_stream_line(line: &str, is_ollama: bool) -> Option<String> {
+        let line = line.trim();
+        if line.is_empty() {
+            return None;
+        }
+
+        if is_ollama {
+            let chunk: ChatChunk_Ollama = serde_json::from_str(line).ok()?;
+            if chunk.done {
+                return None;
+            }
+            return chunk.message.content;
+        }
+
+        // OpenAI-style SSE: lines are prefixed with "data: " and end with a [DONE] sentinel
+        let payload = line.strip_prefix("data:")?.trim();
+        if payload == "[DONE]" {
+            return None;
+        }
+        let chunk: StreamChunk = serde_json::from_str(payload).ok()?;
+        chunk.choices.into_iter().next()?.delta.content
     }
 
     // Extracts JSON from a string, either an object or array
     pub fn extract_json<T: DeserializeOwned>(&self, input: &str, is_object: bool) -> Option<T> {
         let start_char = if is_object { '{' } else { '[' };
         let start_idx = input.find(start_char)?;
-        
+
         // Track nesting depth to find complete JSON structure
         let mut depth = 0;
         let end_char = if is_object { '}' } else { ']' };
         let mut end_idx = 0;
-        
+
         for (i, c) in input[start_idx..].char_indices() {
             if c == start_char {
                 depth += 1;
@@ -188,14 +893,14 @@ impl LlmProfile {
                 }
             }
         }
-        
+
         // Return None if no valid JSON structure found
         if end_idx == 0 || depth != 0 {
             return None;
         }
-        
+
         let json_str = &input[start_idx..end_idx];
-        
+
         // Attempt to deserialize the extracted JSON
         serde_json::from_str(json_str).ok()
     }
@@ -206,12 +911,12 @@ impl LlmProfile {
         if let Some(result) = self.extract_json::<T>(input, true) {
             return Some(result);
         }
-        
+
         // Then try array
         if let Some(result) = self.extract_json::<T>(input, false) {
             return Some(result);
         }
-        
+
         // Try string value
         let re_string = Regex::new(r#""([^"\\]|\\[\s\S])*""#).ok()?;
         if let Some(m) = re_string.find(input) {
@@ -219,7 +924,7 @@ impl LlmProfile {
                 return Some(value);
             }
         }
-        
+
         // Try scalar value (number, boolean, null)
         let re_scalar = Regex::new(r"\b(true|false|null|-?\d+(\.\d+)?([eE][+-]?\d+)?)\b").ok()?;
         if let Some(m) = re_scalar.find(input) {
@@ -227,7 +932,7 @@ impl LlmProfile {
                 return Some(value);
             }
         }
-        
+
         None
     }
 
@@ -246,6 +951,7 @@ impl LlmProfile {
             temperature,
             max_tokens,
             api_url: String::new(), // Default to empty; filled by provider if needed
+            embedding_model_name: String::new(), // Default to empty; embed() falls back to model_name
         }
     }
 }
@@ -325,6 +1031,24 @@ impl LlmProvider {
         }
     }
 
+    // Returns the default embeddings URL for the provider, parallel to get_completion_url.
+    // Only called for providers `embed()` actually implements (OpenAI-style + Ollama);
+    // anthropic/google are rejected by `embed()` before this is reached, since Anthropic has
+    // no embeddings endpoint and Gemini's uses a request/response shape we don't implement.
+    fn get_embedding_url(&self) -> String {
+        match self {
+            LlmProvider::ollama => "http://localhost:11434/api/embeddings".to_string(),
+            LlmProvider::openai => "https://api.openai.com/v1/embeddings".to_string(),
+            LlmProvider::anthropic | LlmProvider::google => unreachable!("embed() rejects this provider before building a URL"),
+            LlmProvider::xai => "https://api.x.ai/v1/embeddings".to_string(),
+            LlmProvider::mistral => "https://api.mixtral.ai/v1/embeddings".to_string(),
+            LlmProvider::deepseek => "https://api.deepseek.com/v1/embeddings".to_string(),
+            LlmProvider::groq => "https://api.groq.com/openai/v1/embeddings".to_string(),
+            LlmProvider::together => "https://api.together.xyz/v1/embeddings".to_string(),
+            LlmProvider::other => "http://localhost:8000/v1/embeddings".to_string(),
+        }
+    }
+
     // Creates an LlmProvider from a slug string
     fn from_str(slug: &str) -> Self {
         match slug.to_lowercase().as_str() {
@@ -340,6 +1064,65 @@ impl LlmProvider {
             _ => LlmProvider::other,
         }
     }
-}
 
+    // Returns the default model-listing URL for the provider
+    fn get_models_url(&self) -> String {
+        match self {
+            LlmProvider::ollama => "http://localhost:11434/api/tags".to_string(),
+            LlmProvider::openai => "https://api.openai.com/v1/models".to_string(),
+            LlmProvider::anthropic => "https://api.anthropic.com/v1/models".to_string(),
+            LlmProvider::google => "https://generativelanguage.googleapis.com/v1beta/models?key=~api_key~".to_string(),
+            LlmProvider::xai => "https://api.x.ai/v1/models".to_string(),
+            LlmProvider::mistral => "https://api.mixtral.ai/v1/models".to_string(),
+            LlmProvider::deepseek => "https://api.deepseek.com/v1/models".to_string(),
+            LlmProvider::groq => "https://api.groq.com/openai/v1/models".to_string(),
+            LlmProvider::together => "https://api.together.xyz/v1/models".to_string(),
+            LlmProvider::other => "http://localhost:8000/v1/models".to_string(),
+        }
+    }
 
+    // Lists the model names (Ollama) or ids (OpenAI-compatible providers) available at this
+    // provider/endpoint. Pass an empty `api_url` to use the provider's default.
+    pub fn list_models(&self, api_key: &str, api_url: &str) -> Result<Vec<String>, SermoError> {
+        let mut url = if api_url.is_empty() {
+            self.get_models_url()
+        } else {
+            api_url.to_string()
+        };
+        url = url.replace("~api_key~", api_key);
+
+        let mut headers: Vec<String> = Vec::new();
+        if !api_key.is_empty() {
+            headers.push(format!("Authorization: Bearer {}", api_key));
+        }
+        let header_refs: Vec<&str> = headers.iter().map(|h| h.as_str()).collect();
+
+        let body = HttpBody::from_raw_str("");
+        let req = HttpRequest::new("GET", &url, &header_refs, &body);
+        let mut http = HttpClient::builder().browser().build_sync();
+        let res = http.send(&req)
+            .map_err(|e| SermoError::Transport(e.to_string()))?;
+
+        if res.status_code() != 200 {
+            let retry_after = res.header("Retry-After").and_then(|v| v.parse::<u64>().ok());
+            return Err(classify_http_error(res.status_code(), &res.body(), retry_after));
+        }
+
+        if *self == LlmProvider::ollama {
+            let parsed: OllamaTagsResponse = serde_json::from_str(&res.body())
+                .map_err(|e| SermoError::Deserialize(e.to_string()))?;
+            Ok(parsed.models.into_iter().map(|m| m.name).collect())
+        } else {
+            let parsed: ModelsListResponse = serde_json::from_str(&res.body())
+                .map_err(|e| SermoError::Deserialize(e.to_string()))?;
+            Ok(parsed.data.into_iter().map(|m| m.id).collect())
+        }
+    }
+
+    // Lightweight reachability check built on the same model-listing call, so a GUI can show
+    // whether the server is up before triggering a (potentially slow, e.g. local Ollama model
+    // load) first inference
+    pub fn is_available(&self, api_key: &str, api_url: &str) -> bool {
+        self.list_models(api_key, api_url).is_ok()
+    }
+}